@@ -0,0 +1,206 @@
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_TABLE,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+    traits::{IsIdentity, VartimeMultiscalarMul},
+};
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha512};
+use std::convert::TryInto;
+
+use holochain_core_types::agent::Base32;
+
+use crate::utils;
+
+/// One signed message to verify: the claimed signer, the message bytes, and
+/// the signature produced over them.
+///
+/// Taken by shared reference rather than the request's `&mut [...]`: the
+/// aggregate check below only ever reads each item once to build the
+/// verification equation, it never reuses or mutates a scalar in place, so
+/// there's nothing `&mut` would buy here.
+pub type BatchItem<'a> = (Base32, &'a [u8], &'a [u8]);
+
+/// Verify many Ed25519 signatures at once using the standard aggregated
+/// batch equation:
+///
+/// `[Σ z_i·s_i]·B = Σ z_i·R_i + Σ(z_i·h_i)·A_i`
+///
+/// with an independent random scalar `z_i` per entry. This is a single
+/// multiscalar multiplication instead of N individual signature checks. If
+/// the aggregate check fails (a forged or corrupted entry is present),
+/// falls back to checking each signature individually so the caller learns
+/// exactly which index(es) are invalid.
+/// @param {&[BatchItem]} items - the (public_key, message, signature) triples to verify
+/// @return {Vec<bool>} per-item verification result, in order
+pub fn verify_batch(items: &[BatchItem]) -> Vec<bool> {
+    if aggregate_check(items) {
+        return vec![true; items.len()];
+    }
+    items.iter().map(|item| verify_single(item)).collect()
+}
+
+/// All-or-nothing form of [`verify_batch`]: true only if every signature in
+/// the batch is valid.
+/// @param {&[BatchItem]} items - the (public_key, message, signature) triples to verify
+/// @return true if every item verifies
+pub fn verify_batch_all(items: &[BatchItem]) -> bool {
+    aggregate_check(items) || items.iter().all(|item| verify_single(item))
+}
+
+/// The decoded curve elements and scalars that make up one entry of the
+/// batch equation: `R` and `A` as curve points, `s` the signature scalar,
+/// and `h = H(R || A || M)` reduced mod the group order.
+struct ParsedItem {
+    r: EdwardsPoint,
+    s: Scalar,
+    a: EdwardsPoint,
+    h: Scalar,
+}
+
+fn parse(item: &BatchItem) -> Option<ParsedItem> {
+    let (public_key, message, signature) = item;
+    if signature.len() != 64 {
+        return None;
+    }
+
+    let public_key_buf = utils::decode_pub_key(public_key).ok()?;
+    let a_bytes: [u8; 32] = public_key_buf.read_lock()[0..32].try_into().ok()?;
+    let r_bytes: [u8; 32] = signature[0..32].try_into().ok()?;
+    let s_bytes: [u8; 32] = signature[32..64].try_into().ok()?;
+
+    // reject non-canonical S, same as a correct individual verify would
+    let s = Scalar::from_canonical_bytes(s_bytes)?;
+    let r = CompressedEdwardsY(r_bytes).decompress()?;
+    let a = CompressedEdwardsY(a_bytes).decompress()?;
+
+    let mut hasher = Sha512::new();
+    hasher.update(&r_bytes);
+    hasher.update(&a_bytes);
+    hasher.update(message);
+    let h = Scalar::from_hash(hasher);
+
+    Some(ParsedItem { r, s, a, h })
+}
+
+/// Check `[Σ z_i·s_i]·B = Σ z_i·R_i + Σ(z_i·h_i)·A_i` for independent random
+/// `z_i`, via a single multiscalar multiplication checked against identity:
+/// `-[Σ z_i·s_i]·B + Σ z_i·R_i + Σ(z_i·h_i)·A_i == O`
+fn aggregate_check(items: &[BatchItem]) -> bool {
+    if items.is_empty() {
+        return true;
+    }
+
+    let parsed: Option<Vec<ParsedItem>> = items.iter().map(parse).collect();
+    let parsed = match parsed {
+        Some(parsed) => parsed,
+        None => return false,
+    };
+
+    let mut rng = OsRng;
+    let zs: Vec<Scalar> = (0..parsed.len()).map(|_| random_scalar(&mut rng)).collect();
+
+    let b_coefficient: Scalar = zs
+        .iter()
+        .zip(&parsed)
+        .fold(Scalar::zero(), |acc, (z, item)| acc + z * item.s);
+
+    let mut scalars = Vec::with_capacity(1 + 2 * parsed.len());
+    let mut points = Vec::with_capacity(1 + 2 * parsed.len());
+
+    scalars.push(-b_coefficient);
+    points.push(ED25519_BASEPOINT_TABLE.basepoint());
+
+    for (z, item) in zs.iter().zip(&parsed) {
+        scalars.push(*z);
+        points.push(item.r);
+    }
+    for (z, item) in zs.iter().zip(&parsed) {
+        scalars.push(z * item.h);
+        points.push(item.a);
+    }
+
+    EdwardsPoint::vartime_multiscalar_mul(scalars.iter(), points.iter()).is_identity()
+}
+
+/// The single-signature form of the same equation (`z = 1`), used only to
+/// report which index(es) failed once the aggregate check has already
+/// failed.
+fn verify_single(item: &BatchItem) -> bool {
+    let parsed = match parse(item) {
+        Some(parsed) => parsed,
+        None => return false,
+    };
+
+    let lhs = &parsed.s * &ED25519_BASEPOINT_TABLE;
+    let rhs = parsed.r + parsed.h * parsed.a;
+    lhs == rhs
+}
+
+fn random_scalar(rng: &mut OsRng) -> Scalar {
+    let mut bytes = [0u8; 64];
+    rng.fill_bytes(&mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{key_bundle::KeyBundle, utils::generate_random_seed_buf};
+    use lib3h_sodium::secbuf::SecBuf;
+
+    fn signed_item(message: &'static [u8]) -> (Base32, &'static [u8], Vec<u8>) {
+        let mut seed = generate_random_seed_buf();
+        let mut bundle = KeyBundle::new_from_seed_buf(&mut seed).unwrap();
+
+        let mut data = SecBuf::with_insecure(message.len());
+        data.write_lock().copy_from_slice(message);
+        let signature = bundle.sign(&mut data).unwrap();
+
+        (bundle.get_id(), message, signature.read_lock()[..].to_vec())
+    }
+
+    #[test]
+    fn verify_batch_should_pass_when_all_signatures_are_valid() {
+        let items: Vec<_> = [&b"one"[..], &b"two"[..], &b"three"[..]]
+            .iter()
+            .map(|message| signed_item(message))
+            .collect();
+        let batch: Vec<BatchItem> = items
+            .iter()
+            .map(|(public_key, message, signature)| (public_key.clone(), *message, signature.as_slice()))
+            .collect();
+
+        assert_eq!(vec![true, true, true], verify_batch(&batch));
+        assert!(verify_batch_all(&batch));
+    }
+
+    #[test]
+    fn verify_batch_should_report_exactly_the_failing_index() {
+        let items: Vec<_> = [&b"one"[..], &b"two"[..], &b"three"[..]]
+            .iter()
+            .map(|message| signed_item(message))
+            .collect();
+        let mut signatures: Vec<Vec<u8>> = items.iter().map(|(_, _, s)| s.clone()).collect();
+        // corrupt the signature of the second item
+        signatures[1][0] ^= 0xff;
+
+        let batch: Vec<BatchItem> = items
+            .iter()
+            .zip(signatures.iter())
+            .map(|((public_key, message, _), signature)| {
+                (public_key.clone(), *message, signature.as_slice())
+            })
+            .collect();
+
+        assert_eq!(vec![true, false, true], verify_batch(&batch));
+        assert!(!verify_batch_all(&batch));
+    }
+
+    #[test]
+    fn verify_batch_should_pass_on_an_empty_batch() {
+        let batch: Vec<BatchItem> = Vec::new();
+        assert_eq!(Vec::<bool>::new(), verify_batch(&batch));
+        assert!(verify_batch_all(&batch));
+    }
+}
@@ -4,6 +4,7 @@ use lib3h_sodium::{kx, secbuf::SecBuf, sign, *};
 use crate::{
     keypair::*,
     password_encryption::{self, EncryptedData, PwHashConfig},
+    secp256k1_keypair::{self, Secp256k1KeyPair, Secp256k1PublicKey, RECOVERABLE_SIGNATURE_SIZE},
     seed::{Seed, SeedType},
     utils, SEED_SIZE,
 };
@@ -17,15 +18,21 @@ use serde_derive::{Deserialize, Serialize};
 pub struct KeyBundle {
     pub sign_keys: SigningKeyPair,
     pub enc_keys: EncryptingKeyPair,
+    pub eth_keys: Secp256k1KeyPair,
 }
 
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_DPKI)]
 impl KeyBundle {
     /// create a new KeyBundle
-    pub fn new(sign_keys: SigningKeyPair, enc_keys: EncryptingKeyPair) -> HcResult<Self> {
+    pub fn new(
+        sign_keys: SigningKeyPair,
+        enc_keys: EncryptingKeyPair,
+        eth_keys: Secp256k1KeyPair,
+    ) -> HcResult<Self> {
         Ok(KeyBundle {
             sign_keys,
             enc_keys,
+            eth_keys,
         })
     }
 
@@ -34,6 +41,7 @@ impl KeyBundle {
         Ok(KeyBundle {
             sign_keys: SigningKeyPair::new_from_seed(&mut seed.buf)?,
             enc_keys: EncryptingKeyPair::new_from_seed(&mut seed.buf)?,
+            eth_keys: Secp256k1KeyPair::new_from_seed(&mut seed.buf)?,
         })
     }
 
@@ -45,6 +53,7 @@ impl KeyBundle {
         Ok(KeyBundle {
             sign_keys: SigningKeyPair::new_from_seed(seed_buf)?,
             enc_keys: EncryptingKeyPair::new_from_seed(seed_buf)?,
+            eth_keys: Secp256k1KeyPair::new_from_seed(seed_buf)?,
         })
     }
 
@@ -88,6 +97,35 @@ impl KeyBundle {
     pub fn is_same(&mut self, other: &mut KeyBundle) -> bool {
         self.sign_keys.is_same(&mut other.sign_keys) && self.enc_keys.is_same(&mut other.enc_keys)
     }
+
+    /// sign a 32-byte message hash with the secp256k1 keys, EVM-style
+    /// @param {[u8; 32]} msg_hash - the hash of the message to sign
+    /// @return {[u8; 65]} signature - the recoverable (r||s||v) signature
+    pub fn sign_eth(&mut self, msg_hash: &[u8; 32]) -> HcResult<[u8; RECOVERABLE_SIGNATURE_SIZE]> {
+        self.eth_keys.sign_recoverable(msg_hash)
+    }
+
+    /// Derive a child KeyBundle from a parent seed without having to persist
+    /// each child seed separately.
+    /// @param {Seed} seed - the parent seed to derive from
+    /// @param {u64} index - the child index
+    /// @param {[u8; 8]} context - the KDF context label, domain-separating subtrees
+    /// @return {KeyBundle} a fully-formed child KeyBundle
+    pub fn derive_child(seed: &mut Seed, index: u64, context: &[u8; 8]) -> HcResult<Self> {
+        let mut child_seed = seed.derive(index, context)?;
+        Self::new_from_seed(&mut child_seed)
+    }
+
+    /// recover the secp256k1 public key that produced a recoverable signature
+    /// @param {[u8; 32]} msg_hash - the hash of the signed message
+    /// @param {[u8; 65]} signature - the recoverable signature to recover from
+    /// @return {Secp256k1PublicKey} the recovered public key
+    pub fn recover_eth(
+        msg_hash: &[u8; 32],
+        signature: &[u8; RECOVERABLE_SIGNATURE_SIZE],
+    ) -> HcResult<Secp256k1PublicKey> {
+        secp256k1_keypair::recover(msg_hash, signature)
+    }
 }
 
 #[cfg(test)]
@@ -112,7 +150,8 @@ pub(crate) mod tests {
         let mut seed = generate_random_seed_buf();
         let sign_keys = SigningKeyPair::new_from_seed(&mut seed).unwrap();
         let enc_keys = EncryptingKeyPair::new_from_seed(&mut seed).unwrap();
-        let result = KeyBundle::new(sign_keys, enc_keys);
+        let eth_keys = Secp256k1KeyPair::new_from_seed(&mut seed).unwrap();
+        let result = KeyBundle::new(sign_keys, enc_keys, eth_keys);
         assert!(result.is_ok());
         let bundle = result.unwrap();
         assert_eq!(64, bundle.sign_keys.private.len());
@@ -0,0 +1,164 @@
+use holochain_core_types::error::{HcResult, HolochainError};
+use lib3h_sodium::{kdf, secbuf::SecBuf};
+use secp256k1::{
+    recovery::{RecoverableSignature, RecoveryId},
+    Message, PublicKey, Secp256k1, SecretKey,
+};
+
+use crate::SEED_SIZE;
+
+/// Size in bytes of a recoverable ECDSA signature: `r || s || v`.
+pub const RECOVERABLE_SIGNATURE_SIZE: usize = 65;
+
+/// KDF context label used to derive the secp256k1 scalar from the root seed.
+/// This domain-separates `eth_keys` from `sign_keys`/`enc_keys` (and from any
+/// HD child derived via `Seed::derive`) so recovering one keypair's private
+/// material never exposes the seed itself or any sibling keypair.
+const SECP256K1_KDF_CONTEXT: &[u8; 8] = b"Secp256k";
+
+/// A hex-encoded, uncompressed secp256k1 public key. Kept as its own type
+/// rather than the crate's `Base32`: `Base32` is a contract other code
+/// decodes via `utils::decode_pub_key` (itself Ed25519/x25519 key material),
+/// and an eth public key isn't that — giving it the same type would let it
+/// slip into a `decode_pub_key` call and fail silently instead of at
+/// compile time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Secp256k1PublicKey(pub String);
+
+/// A secp256k1 keypair, the curve used by Ethereum and most EVM-compatible
+/// Layer-2 chains, so the SDK can produce signatures those chains accept.
+pub struct Secp256k1KeyPair {
+    pub public: Secp256k1PublicKey,
+    pub private: SecBuf,
+}
+
+impl Secp256k1KeyPair {
+    /// Derive the secp256k1 keypair from a 32 bytes seed buffer. The scalar
+    /// is not the seed itself: it is passed through a domain-separated KDF
+    /// first, the same primitive `Seed::derive` uses for HD children.
+    /// @param {SecBuf} seed - the seed buffer
+    pub fn new_from_seed(seed: &mut SecBuf) -> HcResult<Self> {
+        assert_eq!(seed.len(), SEED_SIZE);
+
+        let mut scalar = SecBuf::with_secure(SEED_SIZE);
+        kdf::derive_from_key(&mut scalar, 0, *SECP256K1_KDF_CONTEXT, seed)?;
+
+        let secret_key = {
+            let scalar_lock = scalar.read_lock();
+            SecretKey::from_slice(&scalar_lock[..]).map_err(map_secp256k1_error)?
+        };
+
+        let engine = Secp256k1::signing_only();
+        let public_key = PublicKey::from_secret_key(&engine, &secret_key);
+
+        Ok(Secp256k1KeyPair {
+            public: Secp256k1PublicKey(to_hex(&public_key.serialize_uncompressed())),
+            private: scalar,
+        })
+    }
+
+    /// the hex-encoded public key for this keypair
+    pub fn public(&self) -> Secp256k1PublicKey {
+        self.public.clone()
+    }
+
+    /// Produce a 65-byte recoverable ECDSA signature (`r || s || v`) over a
+    /// 32-byte message hash, the format EVM nodes expect for transactions.
+    /// @param {[u8; 32]} msg_hash - the hash of the message to sign
+    /// @return {[u8; 65]} signature - the recoverable signature
+    pub fn sign_recoverable(&mut self, msg_hash: &[u8; 32]) -> HcResult<[u8; RECOVERABLE_SIGNATURE_SIZE]> {
+        let engine = Secp256k1::signing_only();
+        let message = Message::from_slice(msg_hash).map_err(map_secp256k1_error)?;
+        let secret_key = {
+            let private_lock = self.private.read_lock();
+            SecretKey::from_slice(&private_lock[..]).map_err(map_secp256k1_error)?
+        };
+
+        let recoverable_sig = engine.sign_recoverable(&message, &secret_key);
+        let (recovery_id, compact) = recoverable_sig.serialize_compact();
+
+        let mut signature = [0u8; RECOVERABLE_SIGNATURE_SIZE];
+        signature[0..64].copy_from_slice(&compact);
+        signature[64] = recovery_id.to_i32() as u8;
+        Ok(signature)
+    }
+}
+
+/// Recover the signing public key from a recoverable ECDSA signature and the
+/// message hash it was produced over, without needing the private keypair.
+/// @param {[u8; 32]} msg_hash - the hash of the signed message
+/// @param {[u8; 65]} signature - the recoverable signature, as produced by `sign_recoverable`
+/// @return {Secp256k1PublicKey} the recovered public key
+pub fn recover(
+    msg_hash: &[u8; 32],
+    signature: &[u8; RECOVERABLE_SIGNATURE_SIZE],
+) -> HcResult<Secp256k1PublicKey> {
+    let engine = Secp256k1::verification_only();
+    let message = Message::from_slice(msg_hash).map_err(map_secp256k1_error)?;
+    let recovery_id = RecoveryId::from_i32(i32::from(signature[64])).map_err(map_secp256k1_error)?;
+    let recoverable_sig =
+        RecoverableSignature::from_compact(&signature[0..64], recovery_id).map_err(map_secp256k1_error)?;
+
+    let public_key = engine
+        .recover(&message, &recoverable_sig)
+        .map_err(map_secp256k1_error)?;
+
+    Ok(Secp256k1PublicKey(to_hex(&public_key.serialize_uncompressed())))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn map_secp256k1_error(error: secp256k1::Error) -> HolochainError {
+    use secp256k1::Error::*;
+    let message = match error {
+        InvalidMessage => "invalid secp256k1 message",
+        InvalidPublicKey => "invalid secp256k1 public key",
+        InvalidSignature => "invalid secp256k1 signature",
+        InvalidSecretKey => "invalid secp256k1 secret key",
+        _ => "secp256k1 error",
+    };
+    HolochainError::ErrorGeneric(message.to_string())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::generate_random_seed_buf;
+
+    #[test]
+    fn secp256k1_keypair_should_sign_and_recover() {
+        let mut seed = generate_random_seed_buf();
+        let mut keys = Secp256k1KeyPair::new_from_seed(&mut seed).unwrap();
+
+        let msg_hash = [42u8; 32];
+        let signature = keys.sign_recoverable(&msg_hash).unwrap();
+
+        let recovered = recover(&msg_hash, &signature).unwrap();
+        assert_eq!(keys.public(), recovered);
+    }
+
+    #[test]
+    fn secp256k1_keypair_should_fail_recover_with_wrong_hash() {
+        let mut seed = generate_random_seed_buf();
+        let mut keys = Secp256k1KeyPair::new_from_seed(&mut seed).unwrap();
+
+        let msg_hash = [42u8; 32];
+        let signature = keys.sign_recoverable(&msg_hash).unwrap();
+
+        let other_hash = [7u8; 32];
+        let recovered = recover(&other_hash, &signature).unwrap();
+        assert_ne!(keys.public(), recovered);
+    }
+
+    #[test]
+    fn secp256k1_keypair_private_scalar_should_not_equal_the_raw_seed() {
+        let mut seed = generate_random_seed_buf();
+        let seed_bytes = seed.read_lock()[..].to_vec();
+
+        let keys = Secp256k1KeyPair::new_from_seed(&mut seed).unwrap();
+
+        assert_ne!(seed_bytes, keys.private.read_lock()[..].to_vec());
+    }
+}
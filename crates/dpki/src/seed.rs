@@ -0,0 +1,83 @@
+use lib3h_sodium::{kdf, secbuf::SecBuf};
+
+use crate::SEED_SIZE;
+use holochain_core_types::error::HcResult;
+
+/// The different purposes a [Seed](Seed) can be used for within the DPKI
+/// key hierarchy.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeedType {
+    Root,
+    Revocation,
+    Device,
+    DevicePin,
+    Application,
+    OneShot,
+    Mnemonic,
+}
+
+/// A 32-byte secret from which a [KeyBundle](crate::key_bundle::KeyBundle)
+/// can be deterministically derived.
+pub struct Seed {
+    pub kind: SeedType,
+    pub buf: SecBuf,
+}
+
+impl Seed {
+    /// wrap a raw 32 bytes seed buffer together with its purpose
+    /// @param {SecBuf} buf - the seed buffer
+    /// @param {SeedType} kind - the purpose this seed is used for
+    pub fn new(buf: SecBuf, kind: SeedType) -> Self {
+        assert_eq!(buf.len(), SEED_SIZE);
+        Seed { kind, buf }
+    }
+
+    /// Deterministically derive a child seed from this seed using a keyed
+    /// KDF over the parent seed buffer, the little-endian child index, and
+    /// an 8-byte domain-separation context label. The same (seed, index,
+    /// context) always yields the same child seed, and different contexts
+    /// yield independent subtrees.
+    /// @param {u64} index - the child index
+    /// @param {[u8; 8]} context - the KDF context label, domain-separating subtrees
+    /// @return {Seed} the derived child seed, carrying its parent's SeedType
+    pub fn derive(&mut self, index: u64, context: &[u8; 8]) -> HcResult<Seed> {
+        let mut child_buf = SecBuf::with_secure(SEED_SIZE);
+        kdf::derive_from_key(&mut child_buf, index, *context, &mut self.buf)?;
+        Ok(Seed::new(child_buf, self.kind.clone()))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::generate_random_seed_buf;
+
+    fn test_seed() -> Seed {
+        Seed::new(generate_random_seed_buf(), SeedType::Root)
+    }
+
+    #[test]
+    fn seed_derive_should_be_deterministic() {
+        let mut seed = test_seed();
+        let child_a = seed.derive(0, b"AppOne__").unwrap();
+        let child_b = seed.derive(0, b"AppOne__").unwrap();
+        assert_eq!(child_a.buf.read_lock()[..], child_b.buf.read_lock()[..]);
+    }
+
+    #[test]
+    fn seed_derive_should_domain_separate_by_index_and_context() {
+        let mut seed = test_seed();
+        let child_index_0 = seed.derive(0, b"AppOne__").unwrap();
+        let child_index_1 = seed.derive(1, b"AppOne__").unwrap();
+        assert_ne!(
+            child_index_0.buf.read_lock()[..],
+            child_index_1.buf.read_lock()[..]
+        );
+
+        let child_other_context = seed.derive(0, b"AppTwo__").unwrap();
+        assert_ne!(
+            child_index_0.buf.read_lock()[..],
+            child_other_context.buf.read_lock()[..]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use lib3h_sodium::secbuf::SecBuf;
+
+use crate::{
+    password_encryption::{self, EncryptedData, PwHashConfig},
+    seed::{Seed, SeedType},
+    SEED_SIZE,
+};
+use holochain_core_types::error::{HcResult, HolochainError};
+use serde_derive::{Deserialize, Serialize};
+
+/// KDF context label mixed into the passphrase so a seed bundle derives a
+/// different encryption key than any other password_encryption consumer
+/// would for the same raw passphrase.
+const SEED_BUNDLE_CONTEXT: &[u8; 8] = b"SeedBndl";
+
+/// A passphrase-locked, self-describing backup of a [Seed](crate::seed::Seed)
+/// together with arbitrary caller-defined application data, suitable for
+/// writing to disk or transferring between devices.
+pub struct SeedBundle;
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_DPKI)]
+impl SeedBundle {
+    /// Encrypt `seed` and `app_data` into a portable, passphrase-locked blob.
+    /// The seed's `SeedType` travels inside the encrypted payload so
+    /// unlocking restores the exact same kind of seed that was locked.
+    /// @param {Seed} seed - the seed to back up
+    /// @param {SecBuf} passphrase - the user passphrase to derive the lock key from
+    /// @param {&[u8]} app_data - arbitrary caller-defined bytes stored alongside the seed
+    /// @param {Option<PwHashConfig>} config - Argon2id cost parameters; `None` for production defaults, `Some(..)` (e.g. tests' `TEST_CONFIG`) to use cheaper parameters
+    /// @return {Vec<u8>} a self-contained, serialized blob that can be written to disk
+    pub fn lock(
+        seed: &mut Seed,
+        passphrase: &mut SecBuf,
+        app_data: &[u8],
+        config: Option<PwHashConfig>,
+    ) -> HcResult<Vec<u8>> {
+        let mut plaintext = SecBuf::with_secure(1 + SEED_SIZE + app_data.len());
+        {
+            let mut plaintext = plaintext.write_lock();
+            plaintext[0] = seed_type_to_tag(&seed.kind);
+            let seed_lock = seed.buf.read_lock();
+            plaintext[1..1 + SEED_SIZE].copy_from_slice(&seed_lock[0..SEED_SIZE]);
+            plaintext[1 + SEED_SIZE..].copy_from_slice(app_data);
+        }
+
+        let mut locked_passphrase = Self::context_passphrase(passphrase);
+        let encrypted_data = password_encryption::pw_enc(&mut plaintext, &mut locked_passphrase, config)?;
+
+        serde_json::to_vec(&encrypted_data)
+            .map_err(|e| HolochainError::ErrorGeneric(format!("could not serialize seed bundle: {}", e)))
+    }
+
+    /// Decrypt a blob produced by [`lock`](SeedBundle::lock) back into its
+    /// seed (with its original `SeedType` intact) and application data.
+    /// @param {&[u8]} bytes - the locked bundle, as produced by `lock`
+    /// @param {SecBuf} passphrase - the passphrase the bundle was locked with
+    /// @param {Option<PwHashConfig>} config - must match the config the bundle was locked with
+    /// @return {(Seed, Vec<u8>)} the recovered seed and its app data, unmodified
+    pub fn unlock(
+        bytes: &[u8],
+        passphrase: &mut SecBuf,
+        config: Option<PwHashConfig>,
+    ) -> HcResult<(Seed, Vec<u8>)> {
+        let encrypted_data: EncryptedData = serde_json::from_slice(bytes)
+            .map_err(|e| HolochainError::ErrorGeneric(format!("could not parse seed bundle: {}", e)))?;
+
+        let mut locked_passphrase = Self::context_passphrase(passphrase);
+        let plaintext = password_encryption::pw_dec(&encrypted_data, &mut locked_passphrase, config)?;
+
+        let mut seed_buf = SecBuf::with_secure(SEED_SIZE);
+        let (kind, app_data) = {
+            let plaintext_lock = plaintext.read_lock();
+            let kind = tag_to_seed_type(plaintext_lock[0])?;
+            let mut seed_lock = seed_buf.write_lock();
+            seed_lock.copy_from_slice(&plaintext_lock[1..1 + SEED_SIZE]);
+            (kind, plaintext_lock[1 + SEED_SIZE..].to_vec())
+        };
+
+        Ok((Seed::new(seed_buf, kind), app_data))
+    }
+
+    /// Mix the fixed KDF context label into the passphrase before it reaches
+    /// `password_encryption`, domain-separating seed bundles from any other
+    /// passphrase-derived secret in the crate. Built in a `with_secure`
+    /// buffer since, unlike the label, the passphrase itself is secret.
+    fn context_passphrase(passphrase: &mut SecBuf) -> SecBuf {
+        let mut context_passphrase =
+            SecBuf::with_secure(passphrase.len() + SEED_BUNDLE_CONTEXT.len());
+        {
+            let mut out = context_passphrase.write_lock();
+            let passphrase_lock = passphrase.read_lock();
+            out[0..passphrase.len()].copy_from_slice(&passphrase_lock[..]);
+            out[passphrase.len()..].copy_from_slice(SEED_BUNDLE_CONTEXT);
+        }
+        context_passphrase
+    }
+}
+
+fn seed_type_to_tag(kind: &SeedType) -> u8 {
+    match kind {
+        SeedType::Root => 0,
+        SeedType::Revocation => 1,
+        SeedType::Device => 2,
+        SeedType::DevicePin => 3,
+        SeedType::Application => 4,
+        SeedType::OneShot => 5,
+        SeedType::Mnemonic => 6,
+    }
+}
+
+fn tag_to_seed_type(tag: u8) -> HcResult<SeedType> {
+    Ok(match tag {
+        0 => SeedType::Root,
+        1 => SeedType::Revocation,
+        2 => SeedType::Device,
+        3 => SeedType::DevicePin,
+        4 => SeedType::Application,
+        5 => SeedType::OneShot,
+        6 => SeedType::Mnemonic,
+        _ => {
+            return Err(HolochainError::ErrorGeneric(
+                "unknown SeedType tag in seed bundle".to_string(),
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{key_bundle::tests::TEST_CONFIG, utils::generate_random_seed_buf};
+
+    fn test_passphrase() -> SecBuf {
+        let mut passphrase = SecBuf::with_insecure(16);
+        passphrase.randomize();
+        passphrase
+    }
+
+    #[test]
+    fn seed_bundle_should_lock_and_unlock() {
+        let mut seed = Seed::new(generate_random_seed_buf(), SeedType::Device);
+        let app_data = b"{\"app\":\"test\"}".to_vec();
+        let mut passphrase = test_passphrase();
+
+        let locked = SeedBundle::lock(&mut seed, &mut passphrase, &app_data, TEST_CONFIG).unwrap();
+
+        let (mut unlocked_seed, unlocked_app_data) =
+            SeedBundle::unlock(&locked, &mut passphrase, TEST_CONFIG).unwrap();
+
+        assert_eq!(app_data, unlocked_app_data);
+        assert_eq!(SeedType::Device, unlocked_seed.kind);
+        assert_eq!(
+            seed.buf.read_lock()[..],
+            unlocked_seed.buf.read_lock()[..]
+        );
+    }
+
+    #[test]
+    fn seed_bundle_should_fail_unlock_with_wrong_passphrase() {
+        let mut seed = Seed::new(generate_random_seed_buf(), SeedType::Root);
+        let mut passphrase = test_passphrase();
+        let mut wrong_passphrase = test_passphrase();
+
+        let locked = SeedBundle::lock(&mut seed, &mut passphrase, b"", TEST_CONFIG).unwrap();
+
+        assert!(SeedBundle::unlock(&locked, &mut wrong_passphrase, TEST_CONFIG).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{key_bundle::KeyBundle, utils};
+use holochain_core_types::{agent::Base32, error::HcResult};
+use lib3h_sodium::{secbuf::SecBuf, sign};
+use serde_derive::{Deserialize, Serialize};
+
+/// The signer's public key and their hex-encoded signature over a payload,
+/// travelling alongside it so a peer can authenticate the message.
+pub type Provenance = (Base32, String);
+
+/// An arbitrary payload together with the provenance needed to authenticate
+/// it, suitable for travelling over a JSON wire protocol.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedMessage {
+    pub payload: Vec<u8>,
+    pub provenance: Provenance,
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_DPKI)]
+impl KeyBundle {
+    /// Sign a payload and wrap it with this bundle's provenance.
+    /// @param {&[u8]} payload - the data to sign
+    /// @return {SignedMessage} the payload, signature, and signer public key
+    pub fn sign_message(&mut self, payload: &[u8]) -> HcResult<SignedMessage> {
+        let mut data = SecBuf::with_insecure(payload.len());
+        data.write_lock().copy_from_slice(payload);
+
+        let signature = self.sign(&mut data)?;
+        let signature_hex = to_hex(&signature.read_lock()[..]);
+
+        Ok(SignedMessage {
+            payload: payload.to_vec(),
+            provenance: (self.get_id(), signature_hex),
+        })
+    }
+}
+
+impl SignedMessage {
+    /// Verify this message's signature against its own payload and
+    /// provenance, without needing the signer's private KeyBundle.
+    /// @return true if the signature is valid for this payload and public key
+    pub fn verify(&self) -> bool {
+        let (public_key, signature_hex) = &self.provenance;
+
+        let signature_bytes = match from_hex(signature_hex) {
+            Some(bytes) => bytes,
+            None => return false,
+        };
+
+        let mut data = SecBuf::with_insecure(self.payload.len());
+        data.write_lock().copy_from_slice(&self.payload);
+
+        let mut signature = SecBuf::with_insecure(signature_bytes.len());
+        signature.write_lock().copy_from_slice(&signature_bytes);
+
+        let mut public_key_buf = match utils::decode_pub_key(public_key) {
+            Ok(buf) => buf,
+            Err(_) => return false,
+        };
+
+        sign::verify(&mut signature, &mut data, &mut public_key_buf).is_ok()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::generate_random_seed_buf;
+
+    fn test_bundle() -> KeyBundle {
+        let mut seed = generate_random_seed_buf();
+        KeyBundle::new_from_seed_buf(&mut seed).unwrap()
+    }
+
+    #[test]
+    fn signed_message_should_verify() {
+        let mut bundle = test_bundle();
+        let signed = bundle.sign_message(b"hello layer2").unwrap();
+        assert!(signed.verify());
+    }
+
+    #[test]
+    fn signed_message_should_fail_verify_when_payload_is_tampered() {
+        let mut bundle = test_bundle();
+        let mut signed = bundle.sign_message(b"hello layer2").unwrap();
+        signed.payload = b"hello layer3".to_vec();
+        assert!(!signed.verify());
+    }
+
+    #[test]
+    fn signed_message_should_round_trip_through_json() {
+        let mut bundle = test_bundle();
+        let signed = bundle.sign_message(b"hello layer2").unwrap();
+
+        let json = serde_json::to_string(&signed).unwrap();
+        let deserialized: SignedMessage = serde_json::from_str(&json).unwrap();
+
+        assert!(deserialized.verify());
+    }
+}